@@ -1,14 +1,17 @@
-use crate::io::driver::{READY_ERROR, READY_READ, READY_WRITE};
+use crate::io::driver::{ready2usize, usize2ready, Handle, READY_ERROR};
 use crate::io::{AsyncRead, AsyncWrite, Registration};
 
 use mio::event::Source;
+use mio::Ready;
+use std::cell::UnsafeCell;
 use std::fmt;
 use std::io::{self, Read, Write};
 use std::marker::Unpin;
 use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::Relaxed;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Mutex, Once};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 
 cfg_io_driver! {
     /// Associates an I/O resource that implements the [`std::io::Read`] and/or
@@ -24,12 +27,14 @@ cfg_io_driver! {
     /// implementations using the underlying I/O resource as well as readiness
     /// events provided by the reactor.
     ///
-    /// **Note**: While `PollSource` is `Sync` (if the underlying I/O type is
-    /// `Sync`), the caller must ensure that there are at most two tasks that
-    /// use a `PollSource` instance concurrently. One for reading and one for
-    /// writing. While violating this requirement is "safe" from a Rust memory
-    /// model point of view, it will result in unexpected behavior in the form
-    /// of lost notifications and tasks hanging.
+    /// **Note**: `PollSource` is `Sync` (if the underlying I/O type is
+    /// `Sync`), and any number of tasks may call [`poll_read_ready`] or
+    /// [`poll_write_ready`] concurrently. Every task that observes
+    /// `Poll::Pending` is recorded and woken once matching readiness
+    /// arrives, so multiple readers (or writers) sharing a `PollSource`
+    /// will not miss notifications or hang. At most 128 waiting tasks are
+    /// recorded per direction; past that, the task that has waited longest
+    /// without re-polling is dropped from the list and is not woken.
     ///
     /// ## Readiness events
     ///
@@ -56,8 +61,6 @@ cfg_io_driver! {
     /// ```rust
     /// use tokio::io::PollSource;
     ///
-    /// use futures::ready;
-    /// use mio::Ready;
     /// use mio::net::{TcpStream, TcpListener};
     /// use std::io;
     /// use std::task::{Context, Poll};
@@ -68,27 +71,22 @@ cfg_io_driver! {
     ///
     /// impl MyListener {
     ///     pub fn poll_accept(&mut self, cx: &mut Context<'_>) -> Poll<Result<TcpStream, io::Error>> {
-    ///         let ready = Ready::readable();
-    ///
-    ///         ready!(self.poll_evented.poll_read_ready(cx, ready))?;
-    ///
-    ///         match self.poll_evented.get_ref().accept() {
-    ///             Ok((socket, _)) => Poll::Ready(Ok(socket)),
-    ///             Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-    ///                 self.poll_evented.clear_read_ready(cx, ready)?;
-    ///                 Poll::Pending
-    ///             }
-    ///             Err(e) => Poll::Ready(Err(e)),
-    ///         }
+    ///         self.poll_evented
+    ///             .poll_read_with(cx, |io| io.accept().map(|(socket, _)| socket))
     ///     }
     /// }
     /// ```
     ///
     /// ## Platform-specific events
     ///
-    /// `PollSource` also allows receiving platform-specific `mio::Ready` events.
-    /// These events are included as part of the read readiness event stream. The
-    /// write readiness event stream is only for `Ready::writable()` events.
+    /// `PollSource` also allows waiting on platform-specific `mio::Ready`
+    /// events, such as TCP out-of-band data (`EPOLLPRI`/`POLLPRI`) or HUP,
+    /// via [`poll_ready`] and [`clear_ready`], which take an explicit
+    /// `Ready` mask instead of hard-coding `readable`/`writable`.
+    /// [`poll_read_ready`] and [`poll_write_ready`] are thin wrappers over
+    /// these for the common case. Platform-specific bits are delivered as
+    /// part of the read readiness event stream; the write readiness event
+    /// stream is only for `Ready::writable()` (and `hup`) events.
     ///
     /// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
     /// [`std::io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
@@ -101,6 +99,8 @@ cfg_io_driver! {
     /// [`clear_write_ready`]: #method.clear_write_ready
     /// [`poll_read_ready`]: #method.poll_read_ready
     /// [`poll_write_ready`]: #method.poll_write_ready
+    /// [`poll_ready`]: #method.poll_ready
+    /// [`clear_ready`]: #method.clear_ready
     pub struct PollSource<E: Source> {
         io: Option<E>,
         inner: Inner,
@@ -108,19 +108,130 @@ cfg_io_driver! {
 }
 
 struct Inner {
-    registration: Registration,
+    /// Guards one-time registration with the reactor. Registration happens
+    /// lazily, on the first call to `PollSource::register`, rather than at
+    /// construction time.
+    register: Once,
+
+    /// Reactor handle to register with, if one was given explicitly at
+    /// construction. `None` means "bind to whatever reactor is current when
+    /// registration actually happens".
+    handle: Option<Handle>,
+
+    /// Populated by `register`'s `call_once` closure the first (and only)
+    /// time it runs. `Once` establishes a happens-before relationship
+    /// between that write and every read performed after
+    /// `register.is_completed()` is observed to be true, so reading this
+    /// through a shared reference once registration has completed is sound.
+    registration: UnsafeCell<Option<Registration>>,
+
+    /// Set instead of `registration` if the deferred registration failed.
+    /// Surfaced on every subsequent call, same synchronization as above.
+    registration_error: UnsafeCell<Option<io::Error>>,
 
     /// Currently visible read readiness
     read_readiness: AtomicUsize,
 
     /// Currently visible write readiness
     write_readiness: AtomicUsize,
+
+    /// Wakers of every task currently waiting on read readiness. More than
+    /// one task may wait at a time; all of them are woken when readiness
+    /// arrives. `Arc`'d because a [`proxy_waker`] built from this list is
+    /// itself what gets registered with the reactor (see below), and that
+    /// waker must stay valid independent of where `Inner` lives.
+    readers: Arc<Mutex<Vec<Waker>>>,
+
+    /// Wakers of every task currently waiting on write readiness.
+    writers: Arc<Mutex<Vec<Waker>>>,
+}
+
+/// Upper bound on how many distinct wakers are remembered per direction.
+///
+/// Tasks that observe `Pending` and are then dropped without being polled
+/// again (e.g. cancelled by `select!`/timeouts) leave their cloned waker
+/// behind until readiness arrives and `wake_all` drains the list. This cap
+/// bounds that leak: once hit, the oldest (least recently (re-)registered)
+/// waker is evicted to make room, trading a theoretical missed wakeup for
+/// that one stale waiter against unbounded memory growth.
+const MAX_WAITERS: usize = 128;
+
+/// Records `waker` as waiting, unless an equivalent waker (per
+/// [`Waker::will_wake`]) is already present.
+fn push_waker(list: &Arc<Mutex<Vec<Waker>>>, waker: &Waker) {
+    let mut list = list.lock().unwrap();
+    if let Some(pos) = list.iter().position(|w| w.will_wake(waker)) {
+        // Already waiting; move it to the back so eviction targets the
+        // longest-idle waiters first.
+        let w = list.remove(pos);
+        list.push(w);
+        return;
+    }
+
+    if list.len() >= MAX_WAITERS {
+        list.remove(0);
+    }
+    list.push(waker.clone());
+}
+
+/// Wakes and clears every waker currently recorded in `list`.
+fn wake_all(list: &Arc<Mutex<Vec<Waker>>>) {
+    for waker in list.lock().unwrap().drain(..) {
+        waker.wake();
+    }
 }
 
+/// Builds a [`Waker`] that, when woken, drains and wakes every waiter in
+/// `list` (see [`wake_all`]), instead of waking one specific task.
+///
+/// `Registration` only has one slot per direction for the waker it notifies
+/// on readiness — whichever task last called `poll_read_ready`/
+/// `poll_write_ready` "owns" that slot. If that task is then dropped without
+/// re-polling (e.g. cancelled by `select!` or a timeout), nobody is left to
+/// drain `readers`/`writers` and every other waiter would hang forever, even
+/// though the reactor did fire. Passing this proxy to the registration
+/// instead of a task's own waker fixes that: the registration always wakes
+/// *us*, and we fan that out to the full waiter list ourselves, regardless
+/// of whether the task that happened to own the slot is still around.
+fn proxy_waker(list: &Arc<Mutex<Vec<Waker>>>) -> Waker {
+    unsafe fn clone(data: *const ()) -> RawWaker {
+        let list = Arc::from_raw(data as *const Mutex<Vec<Waker>>);
+        let cloned = list.clone();
+        std::mem::forget(list);
+        RawWaker::new(Arc::into_raw(cloned) as *const (), &VTABLE)
+    }
+
+    unsafe fn wake(data: *const ()) {
+        let list = Arc::from_raw(data as *const Mutex<Vec<Waker>>);
+        wake_all(&list);
+    }
+
+    unsafe fn wake_by_ref(data: *const ()) {
+        let list = Arc::from_raw(data as *const Mutex<Vec<Waker>>);
+        wake_all(&list);
+        std::mem::forget(list);
+    }
+
+    unsafe fn drop_raw(data: *const ()) {
+        drop(Arc::from_raw(data as *const Mutex<Vec<Waker>>));
+    }
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_raw);
+
+    let data = Arc::into_raw(list.clone()) as *const ();
+    unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+}
+
+// `UnsafeCell` opts `Inner` out of `Sync`; it's restored here because every
+// write to the cells above happens inside `Once::call_once`, which only ever
+// runs the closure on one thread at a time and establishes happens-before
+// for readers that see it complete.
+unsafe impl Sync for Inner {}
+
 // ===== impl PollSource =====
 
 macro_rules! poll_ready {
-    ($me:expr, $mask:expr, $cache:ident, $take:ident, $poll:expr) => {{
+    ($me:expr, $registration:expr, $mask:expr, $cache:ident, $take:ident, $wakers:ident, $cx:expr, $method:ident) => {{
         // Load cached & encoded readiness.
         let mut cached = $me.inner.$cache.load(Relaxed);
         let mask = $mask | READY_ERROR;
@@ -132,8 +243,27 @@ macro_rules! poll_ready {
             // Readiness does not match, consume the registration's readiness
             // stream. This happens in a loop to ensure that the stream gets
             // drained.
-            loop {
-                let ready = match $poll? {
+            //
+            // `break`, not `return`, carries the readiness value out of the
+            // loop: this whole macro expands to a `usize`-valued expression,
+            // and callers convert with `usize2ready` themselves, so a bare
+            // `return` here would bypass that conversion and leak the raw
+            // `usize` out through the caller's `Poll<io::Result<Ready>>`.
+            let ret = loop {
+                // Always hand the registration our own proxy waker, never
+                // `$cx`'s, so that whichever task happens to "own" the
+                // registration's single notification slot, the reactor
+                // always wakes us — and we fan that out to every waiter in
+                // `$wakers` ourselves. See `proxy_waker`.
+                let proxy = proxy_waker(&$me.inner.$wakers);
+                let mut proxy_cx = Context::from_waker(&proxy);
+
+                // Record this task before polling the registration, so that a
+                // readiness event firing the proxy right after the poll below
+                // returns `Pending` still finds this task in `$wakers`.
+                push_waker(&$me.inner.$wakers, $cx.waker());
+
+                let ready = match $registration.$method(&mut proxy_cx)? {
                     Poll::Ready(v) => v,
                     Poll::Pending => return Poll::Pending,
                 };
@@ -145,13 +275,19 @@ macro_rules! poll_ready {
                 ret |= ready & mask;
 
                 if ret != 0 {
-                    return Poll::Ready(Ok(ret));
+                    // Readiness became available; wake every task that was
+                    // waiting on it (this one included, as it was recorded
+                    // above). They'll see it cached on their next poll.
+                    wake_all(&$me.inner.$wakers);
+                    break ret;
                 }
-            }
+            };
+
+            Poll::Ready(Ok(ret))
         } else {
             // Check what's new with the registration stream. This will not
             // request to be notified
-            if let Some(ready) = $me.inner.registration.$take()? {
+            if let Some(ready) = $registration.$take()? {
                 cached |= ready;
                 $me.inner.$cache.store(cached, Relaxed);
             }
@@ -165,25 +301,110 @@ impl<E> PollSource<E>
 where
     E: Source,
 {
-    /// Creates a new `PollSource` associated with the default reactor.
+    /// Creates a new `PollSource`.
     ///
-    /// # Panics
+    /// Registration with the reactor is deferred until the first call to
+    /// one of [`poll_read_ready`], [`poll_write_ready`], [`poll_read`], or
+    /// [`poll_write`], and binds to whatever reactor is current at that
+    /// point. This means a `PollSource` can be constructed outside of a
+    /// task context and moved into one later, unlike eager registration
+    /// which would need a reactor to already be current.
     ///
-    /// This function panics if thread-local runtime is not set.
+    /// Use [`new_with_handle`] instead if the reactor to bind to is already
+    /// known at construction time.
+    ///
+    /// [`poll_read_ready`]: #method.poll_read_ready
+    /// [`poll_write_ready`]: #method.poll_write_ready
+    /// [`poll_read`]: trait@AsyncRead
+    /// [`poll_write`]: trait@AsyncWrite
+    /// [`new_with_handle`]: #method.new_with_handle
+    pub fn new(io: E) -> Self {
+        Self::new_with_inner(io, None)
+    }
+
+    /// Creates a new `PollSource` that will register with `handle` the
+    /// first time it is polled, instead of binding to whatever reactor
+    /// happens to be current at that point.
     ///
-    /// The runtime is usually set implicitly when this function is called
-    /// from a future driven by a tokio runtime, otherwise runtime can be set
-    /// explicitly with [`Handle::enter`](crate::runtime::Handle::enter) function.
-    pub fn new(mut io: E) -> io::Result<Self> {
-        let registration = Registration::new(&mut io)?;
-        Ok(Self {
+    /// As with [`new`], registration itself is still deferred until first
+    /// use.
+    ///
+    /// [`new`]: #method.new
+    pub fn new_with_handle(io: E, handle: Handle) -> Self {
+        Self::new_with_inner(io, Some(handle))
+    }
+
+    fn new_with_inner(io: E, handle: Option<Handle>) -> Self {
+        Self {
             io: Some(io),
             inner: Inner {
-                registration,
+                register: Once::new(),
+                handle,
+                registration: UnsafeCell::new(None),
+                registration_error: UnsafeCell::new(None),
                 read_readiness: AtomicUsize::new(0),
                 write_readiness: AtomicUsize::new(0),
+                readers: Arc::new(Mutex::new(Vec::new())),
+                writers: Arc::new(Mutex::new(Vec::new())),
             },
-        })
+        }
+    }
+
+    /// Registers with the reactor on the first call; returns the
+    /// now-initialized registration (or the error that registration failed
+    /// with) on this and every subsequent call.
+    fn register(&self) -> io::Result<&Registration> {
+        self.inner.register.call_once(|| {
+            let io = self.get_ref();
+
+            let result = match &self.inner.handle {
+                Some(handle) => Registration::new_with_handle(io, handle.clone()),
+                None => Registration::new(io),
+            };
+
+            // Safety: see the comment on `unsafe impl Sync for Inner`.
+            unsafe {
+                match result {
+                    Ok(registration) => *self.inner.registration.get() = Some(registration),
+                    Err(e) => *self.inner.registration_error.get() = Some(e),
+                }
+            }
+        });
+
+        match self.initialized_registration() {
+            Some(registration) => Ok(registration),
+            None => {
+                // Safety: `call_once` above has returned, so if
+                // `registration` is empty, `registration_error` was set.
+                let e = unsafe { (*self.inner.registration_error.get()).as_ref().unwrap() };
+                Err(io::Error::new(e.kind(), e.to_string()))
+            }
+        }
+    }
+
+    /// Returns the registration if it has already been initialized
+    /// (successfully), or `None` if registration hasn't happened yet or
+    /// failed.
+    fn initialized_registration(&self) -> Option<&Registration> {
+        if self.inner.register.is_completed() {
+            // Safety: see the comment on `unsafe impl Sync for Inner`.
+            unsafe { (*self.inner.registration.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Clears the cached readiness bits in `mask`, without touching any
+    /// waiting task. HUP (on platforms that support it) is a final state and
+    /// is never cleared, even if the caller asked for it.
+    fn clear_readiness_bits(&self, mask: Ready) {
+        let raw_mask = ready2usize(mask - Ready::hup_readiness());
+
+        if mask.is_writable() {
+            self.inner.write_readiness.fetch_and(!raw_mask, Relaxed);
+        } else {
+            self.inner.read_readiness.fetch_and(!raw_mask, Relaxed);
+        }
     }
 
     /// Returns a shared reference to the underlying I/O object this readiness
@@ -201,67 +422,102 @@ where
     /// Consumes self, returning the inner I/O object
     ///
     /// This function will deregister the I/O resource from the reactor before
-    /// returning. If the deregistration operation fails, an error is returned.
+    /// returning, provided it was ever registered in the first place. If the
+    /// deregistration operation fails, an error is returned.
     ///
     /// Note that deregistering does not guarantee that the I/O resource can be
     /// registered with a different reactor. Some I/O resource types can only be
     /// associated with a single reactor instance for their lifetime.
     pub fn into_inner(mut self) -> io::Result<E> {
         let mut io = self.io.take().unwrap();
-        self.inner.registration.deregister(&mut io)?;
+        if let Some(registration) = self.initialized_registration() {
+            registration.deregister(&mut io)?;
+        }
         Ok(io)
     }
 
-    /// Checks the I/O resource's read readiness state.
+    /// Checks the I/O resource's readiness for the bits set in `mask`.
     ///
-    /// The mask argument allows specifying what readiness to notify on. This
-    /// can be any value, including platform specific readiness, **except**
-    /// `writable`. HUP is always implicitly included on platforms that support
-    /// it.
+    /// `mask` may be any combination of readable and platform-specific bits
+    /// (priority, out-of-band data, HUP, ...), **or** just `writable`
+    /// (optionally combined with `hup`) — the two cannot be mixed in one
+    /// call, since readable and writable readiness are tracked and
+    /// registered with the reactor separately. HUP is only waited on when
+    /// `mask` includes it; it is not added implicitly.
     ///
-    /// If the resource is not ready for a read then `Poll::Pending` is returned
-    /// and the current task is notified once a new event is received.
+    /// If the resource does not have any of the requested readiness then
+    /// `Poll::Pending` is returned and the current task is notified once a
+    /// matching event is received.
     ///
-    /// The I/O resource will remain in a read-ready state until readiness is
-    /// cleared by calling [`clear_read_ready`].
+    /// The I/O resource will remain ready for the bits returned here until
+    /// readiness is cleared by calling [`clear_ready`] with (a subset of)
+    /// those bits.
     ///
-    /// [`clear_read_ready`]: #method.clear_read_ready
+    /// [`clear_ready`]: #method.clear_ready
     ///
     /// # Panics
     ///
     /// This function panics if:
     ///
-    /// * `ready` includes writable.
+    /// * `mask` combines `writable` with anything other than `hup`.
     /// * called from outside of a task context.
-    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
-        poll_ready!(
-            self,
-            READY_READ,
-            read_readiness,
-            take_read_ready,
-            self.inner.registration.poll_read_ready(cx)
-        )
+    pub fn poll_ready(&self, cx: &mut Context<'_>, mask: Ready) -> Poll<io::Result<Ready>> {
+        let is_write = mask.is_writable();
+        let writable_bits = Ready::writable() | Ready::hup_readiness();
+        assert!(
+            !is_write || (mask | writable_bits) == writable_bits,
+            "mask may only combine `writable` with `hup`"
+        );
+
+        let registration = self.register()?;
+        let raw_mask = ready2usize(mask);
+
+        let raw_ready = if is_write {
+            ready!(poll_ready!(
+                self,
+                registration,
+                raw_mask,
+                write_readiness,
+                take_write_ready,
+                writers,
+                cx,
+                poll_write_ready
+            ))?
+        } else {
+            ready!(poll_ready!(
+                self,
+                registration,
+                raw_mask,
+                read_readiness,
+                take_read_ready,
+                readers,
+                cx,
+                poll_read_ready
+            ))?
+        };
+
+        Poll::Ready(Ok(usize2ready(raw_ready)))
     }
 
-    /// Clears the I/O resource's read readiness state and registers the current
-    /// task to be notified once a read readiness event is received.
+    /// Clears the I/O resource's readiness for the bits set in `mask`, and
+    /// registers the current task to be notified once a matching readiness
+    /// event is received.
     ///
-    /// After calling this function, `poll_read_ready` will return
-    /// `Poll::Pending` until a new read readiness event has been received.
-    ///
-    /// The `mask` argument specifies the readiness bits to clear. This may not
-    /// include `writable` or `hup`.
+    /// After calling this function, `poll_ready` will return `Poll::Pending`
+    /// for these bits until a new matching readiness event has been
+    /// received. HUP (on platforms that support HUP) cannot be cleared, as
+    /// it is a final state.
     ///
     /// # Panics
     ///
     /// This function panics if:
     ///
-    /// * `ready` includes writable or HUP
+    /// * `mask` combines `writable` with anything other than `hup`.
     /// * called from outside of a task context.
-    pub fn clear_read_ready(&self, cx: &mut Context<'_>) -> io::Result<()> {
-        self.inner.read_readiness.fetch_and(!READY_READ, Relaxed);
+    pub fn clear_ready(&self, cx: &mut Context<'_>, mask: Ready) -> io::Result<()> {
+        self.clear_readiness_bits(mask);
 
-        if self.poll_read_ready(cx)?.is_ready() {
+        if self.poll_ready(cx, mask)?.is_ready() {
             // Notify the current task
             cx.waker().wake_by_ref();
         }
@@ -269,56 +525,155 @@ where
         Ok(())
     }
 
-    /// Checks the I/O resource's write readiness state.
+    /// Checks the I/O resource's read readiness state.
     ///
-    /// This always checks for writable readiness and also checks for HUP
-    /// readiness on platforms that support it.
+    /// This is a thin wrapper over [`poll_ready`] for `Ready::readable()`.
+    /// Use [`poll_ready`] directly to observe platform-specific readiness
+    /// such as out-of-band data.
     ///
-    /// If the resource is not ready for a write then `Poll::Pending` is
-    /// returned and the current task is notified once a new event is received.
+    /// [`poll_ready`]: #method.poll_ready
     ///
-    /// The I/O resource will remain in a write-ready state until readiness is
-    /// cleared by calling [`clear_write_ready`].
+    /// # Panics
     ///
-    /// [`clear_write_ready`]: #method.clear_write_ready
+    /// This function panics if called from outside of a task context.
+    pub fn poll_read_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        Poll::Ready(Ok(ready2usize(ready!(
+            self.poll_ready(cx, Ready::readable())
+        )?)))
+    }
+
+    /// Clears the I/O resource's read readiness state and registers the current
+    /// task to be notified once a read readiness event is received.
+    ///
+    /// This is a thin wrapper over [`clear_ready`] for `Ready::readable()`.
+    ///
+    /// [`clear_ready`]: #method.clear_ready
     ///
     /// # Panics
     ///
-    /// This function panics if:
+    /// This function panics if called from outside of a task context.
+    pub fn clear_read_ready(&self, cx: &mut Context<'_>) -> io::Result<()> {
+        self.clear_ready(cx, Ready::readable())
+    }
+
+    /// Checks the I/O resource's write readiness state.
     ///
-    /// * `ready` contains bits besides `writable` and `hup`.
-    /// * called from outside of a task context.
+    /// This always checks for writable readiness and also checks for HUP
+    /// readiness on platforms that support it. This is a thin wrapper over
+    /// [`poll_ready`] for `Ready::writable()`.
+    ///
+    /// [`poll_ready`]: #method.poll_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
     pub fn poll_write_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
-        poll_ready!(
-            self,
-            READY_WRITE,
-            write_readiness,
-            take_write_ready,
-            self.inner.registration.poll_write_ready(cx)
-        )
+        Poll::Ready(Ok(ready2usize(ready!(self
+            .poll_ready(cx, Ready::writable() | Ready::hup_readiness()))?)))
     }
 
     /// Resets the I/O resource's write readiness state and registers the current
     /// task to be notified once a write readiness event is received.
     ///
     /// This only clears writable readiness. HUP (on platforms that support HUP)
-    /// cannot be cleared as it is a final state.
+    /// cannot be cleared as it is a final state. This is a thin wrapper over
+    /// [`clear_ready`] for `Ready::writable()`.
     ///
-    /// After calling this function, `poll_write_ready(Ready::writable())` will
-    /// return `NotReady` until a new write readiness event has been received.
+    /// [`clear_ready`]: #method.clear_ready
     ///
     /// # Panics
     ///
     /// This function will panic if called from outside of a task context.
     pub fn clear_write_ready(&self, cx: &mut Context<'_>) -> io::Result<()> {
-        self.inner.write_readiness.fetch_and(!READY_WRITE, Relaxed);
+        self.clear_ready(cx, Ready::writable())
+    }
 
-        if self.poll_write_ready(cx)?.is_ready() {
-            // Notify the current task
-            cx.waker().wake_by_ref();
+    /// Polls for read readiness, then tries `f` once the I/O resource is
+    /// ready.
+    ///
+    /// This is the same pattern the [`AsyncRead`] impl follows, and is the
+    /// pattern every read-like operation that isn't plain `std::io::Read`
+    /// needs (`accept`, `recv_from`, `recvmsg`, ...). `f` is given a shared
+    /// reference to the wrapped I/O resource and should make a single
+    /// attempt at the operation. If `f` fails with
+    /// [`ErrorKind::WouldBlock`], the read readiness state is cleared and
+    /// the current task is scheduled to be notified when the resource
+    /// becomes readable again; any other result (including success) is
+    /// returned immediately.
+    ///
+    /// [`AsyncRead`]: trait@AsyncRead
+    /// [`ErrorKind::WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
+    pub fn poll_read_with<F, R>(&self, cx: &mut Context<'_>, mut f: F) -> Poll<io::Result<R>>
+    where
+        F: FnMut(&E) -> io::Result<R>,
+    {
+        ready!(self.poll_read_ready(cx))?;
+
+        let r = f(self.get_ref());
+
+        if is_wouldblock(&r) {
+            self.clear_read_ready(cx)?;
+            return Poll::Pending;
         }
 
-        Ok(())
+        Poll::Ready(r)
+    }
+
+    /// Polls for write readiness, then tries `f` once the I/O resource is
+    /// ready.
+    ///
+    /// See [`poll_read_with`] for the full semantics; this is the same
+    /// dance but driven by write readiness, for operations such as
+    /// `send_to` or `sendmsg`.
+    ///
+    /// [`poll_read_with`]: #method.poll_read_with
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
+    pub fn poll_write_with<F, R>(&self, cx: &mut Context<'_>, mut f: F) -> Poll<io::Result<R>>
+    where
+        F: FnMut(&E) -> io::Result<R>,
+    {
+        ready!(self.poll_write_ready(cx))?;
+
+        let r = f(self.get_ref());
+
+        if is_wouldblock(&r) {
+            self.clear_write_ready(cx)?;
+            return Poll::Pending;
+        }
+
+        Poll::Ready(r)
+    }
+
+    /// Tries an I/O operation once, without polling or waiting for readiness
+    /// and without registering the current task to be woken.
+    ///
+    /// This is for callers that already know the resource is ready — e.g.
+    /// inside a loop that already called [`poll_read_with`]/
+    /// [`poll_write_with`] (or `poll_read_ready`/`poll_write_ready`) once and
+    /// wants to retry a few more times against that same readiness event
+    /// before yielding back to the executor. On [`ErrorKind::WouldBlock`],
+    /// the corresponding readiness bits in `mask` are cleared so the next
+    /// `poll_ready`/`poll_read_ready`/`poll_write_ready` call will wait for a
+    /// fresh event; no task is woken, since none was registered here.
+    ///
+    /// [`poll_read_with`]: #method.poll_read_with
+    /// [`poll_write_with`]: #method.poll_write_with
+    /// [`ErrorKind::WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock
+    pub fn try_io<R>(&self, mask: Ready, f: impl FnOnce(&E) -> io::Result<R>) -> io::Result<R> {
+        let r = f(self.get_ref());
+
+        if is_wouldblock(&r) {
+            self.clear_readiness_bits(mask);
+        }
+
+        r
     }
 }
 
@@ -401,8 +756,132 @@ impl<E: Source + fmt::Debug> fmt::Debug for PollSource<E> {
 impl<E: Source> Drop for PollSource<E> {
     fn drop(&mut self) {
         if let Some(mut io) = self.io.take() {
-            // Ignore errors
-            let _ = self.inner.registration.deregister(&mut io);
+            if let Some(registration) = self.initialized_registration() {
+                // Ignore errors
+                let _ = registration.deregister(&mut io);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    /// Builds a `Waker` that increments `counter` every time it's woken,
+    /// for asserting on how many times (and that) a waiter was notified.
+    fn counting_waker(counter: &Arc<AtomicUsize>) -> Waker {
+        unsafe fn clone(data: *const ()) -> RawWaker {
+            let counter = Arc::from_raw(data as *const AtomicUsize);
+            let cloned = counter.clone();
+            std::mem::forget(counter);
+            RawWaker::new(Arc::into_raw(cloned) as *const (), &VTABLE)
+        }
+
+        unsafe fn wake(data: *const ()) {
+            let counter = Arc::from_raw(data as *const AtomicUsize);
+            counter.fetch_add(1, Ordering::SeqCst);
         }
+
+        unsafe fn wake_by_ref(data: *const ()) {
+            let counter = &*(data as *const AtomicUsize);
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+
+        unsafe fn drop_raw(data: *const ()) {
+            drop(Arc::from_raw(data as *const AtomicUsize));
+        }
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_raw);
+
+        let data = Arc::into_raw(counter.clone()) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+    }
+
+    #[test]
+    fn wake_all_wakes_every_registered_waiter() {
+        let list: Arc<Mutex<Vec<Waker>>> = Arc::new(Mutex::new(Vec::new()));
+        let counters: Vec<_> = (0..4).map(|_| Arc::new(AtomicUsize::new(0))).collect();
+
+        for counter in &counters {
+            push_waker(&list, &counting_waker(counter));
+        }
+        assert_eq!(list.lock().unwrap().len(), 4);
+
+        wake_all(&list);
+
+        assert!(list.lock().unwrap().is_empty());
+        for counter in &counters {
+            assert_eq!(counter.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    #[test]
+    fn proxy_waker_wakes_every_waiter_even_if_the_registering_task_is_gone() {
+        let list: Arc<Mutex<Vec<Waker>>> = Arc::new(Mutex::new(Vec::new()));
+        let a = Arc::new(AtomicUsize::new(0));
+        let b = Arc::new(AtomicUsize::new(0));
+
+        // Two tasks observe `Pending` and record themselves as waiting...
+        push_waker(&list, &counting_waker(&a));
+        push_waker(&list, &counting_waker(&b));
+
+        // ...and whichever of them polled last is the one whose own waker
+        // would have been handed straight to `Registration` if we weren't
+        // proxying. Here it just goes out of scope unpolled, standing in
+        // for that task being cancelled before it gets a chance to repoll.
+        // `proxy` is what `Registration` actually holds, and its validity
+        // doesn't depend on either task still existing.
+        let proxy = proxy_waker(&list);
+
+        // Simulates the reactor firing: the registration wakes its one
+        // stored waker, which is always `proxy`.
+        proxy.wake();
+
+        assert_eq!(a.load(Ordering::SeqCst), 1);
+        assert_eq!(b.load(Ordering::SeqCst), 1);
+        assert!(list.lock().unwrap().is_empty());
+    }
+
+    struct FakeSource;
+
+    impl Source for FakeSource {
+        fn register(
+            &self,
+            _poll: &mio::Poll,
+            _token: mio::Token,
+            _interest: Ready,
+            _opts: mio::PollOpt,
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn reregister(
+            &self,
+            _poll: &mio::Poll,
+            _token: mio::Token,
+            _interest: Ready,
+            _opts: mio::PollOpt,
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn deregister(&self, _poll: &mio::Poll) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn new_defers_registration_until_first_poll() {
+        // Constructing a `PollSource` must not require a reactor to be
+        // current — only `register()`, invoked lazily from the first poll,
+        // does. `new_with_handle` shares this same `new_with_inner`
+        // constructor, just with `handle` pre-filled, so the guarantee
+        // carries over without needing a separate `Handle` to test it.
+        let evented = PollSource::new(FakeSource);
+
+        assert!(!evented.inner.register.is_completed());
+        assert!(evented.initialized_registration().is_none());
     }
 }